@@ -1,9 +1,14 @@
-use clap::{Parser, Subcommand};
+use chrono::Utc;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use regex::Regex;
 use std::env;
-use std::fs;
+use std::fs::{self, OpenOptions};
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const VERSION: &str = "0.5.0";
 
@@ -26,11 +31,20 @@ enum Commands {
         name: String,
         /// Value of the variable
         value: String,
+        /// Validate and record the value's type
+        #[arg(long)]
+        r#type: Option<ValueType>,
+        /// Expire the entry after this duration (e.g. 30m, 2h, 7d)
+        #[arg(long, value_parser = parse_ttl)]
+        ttl: Option<Duration>,
     },
     /// Get a briefcase variable
     Get {
         /// Name of the variable
         name: String,
+        /// Emit the entry as a JSON object with its name, type, and value
+        #[arg(long)]
+        json: bool,
     },
     /// Purge briefcase data
     Purge {
@@ -42,9 +56,40 @@ enum Commands {
     Remove {
         /// Name of the variable
         name: String,
+        /// Delete immediately instead of moving to the trash
+        #[arg(long)]
+        permanent: bool,
+    },
+    /// Restore a trashed briefcase variable
+    Restore {
+        /// Name of the variable
+        name: String,
     },
     /// List briefcase entries
-    List,
+    List {
+        /// List trashed entries instead of live ones
+        #[arg(long)]
+        trashed: bool,
+        /// Show each entry's type and byte size
+        #[arg(long)]
+        long: bool,
+    },
+    /// Run a command with a briefcase variable's value injected
+    Exec {
+        /// Name of the variable
+        name: String,
+        /// Command to run; use `{}` as a placeholder for the value, or it
+        /// is appended as the final argument
+        #[arg(last = true)]
+        command: Vec<String>,
+    },
+    /// Generate a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+    /// Remove all expired entries
+    Sweep,
 }
 
 struct TempDir {
@@ -52,11 +97,63 @@ struct TempDir {
     env_var: String,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ValueType {
+    String,
+    Int,
+    Bool,
+    Json,
+}
+
+impl ValueType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ValueType::String => "string",
+            ValueType::Int => "int",
+            ValueType::Bool => "bool",
+            ValueType::Json => "json",
+        }
+    }
+
+    fn parse_label(label: &str) -> Option<Self> {
+        match label {
+            "string" => Some(ValueType::String),
+            "int" => Some(ValueType::Int),
+            "bool" => Some(ValueType::Bool),
+            "json" => Some(ValueType::Json),
+            _ => None,
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<()> {
+        let valid = match self {
+            ValueType::String => true,
+            ValueType::Int => value.parse::<i64>().is_ok(),
+            ValueType::Bool => value.parse::<bool>().is_ok(),
+            ValueType::Json => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(BriefcaseError::InvalidValue(format!(
+                "{} is not a valid {}",
+                value,
+                self.as_str()
+            )))
+        }
+    }
+}
+
 #[derive(Debug)]
 enum BriefcaseError {
     Io(io::Error),
     InvalidEntry(String),
     EntryNotFound(String),
+    LockHeld(String),
+    InvalidCommand(String),
+    InvalidValue(String),
+    EntryExists(String),
 }
 
 impl From<io::Error> for BriefcaseError {
@@ -73,11 +170,15 @@ fn main() -> Result<()> {
     match &cli.command {
         Commands::Version => version(),
         Commands::Info => info()?,
-        Commands::Set { name, value } => set(name, value)?,
-        Commands::Get { name } => get(name)?,
+        Commands::Set { name, value, r#type, ttl } => set(name, value, *r#type, *ttl)?,
+        Commands::Get { name, json } => get(name, *json)?,
         Commands::Purge { force } => purge(*force)?,
-        Commands::Remove { name } => remove(name)?,
-        Commands::List => list()?,
+        Commands::Remove { name, permanent } => remove(name, *permanent)?,
+        Commands::Restore { name } => restore(name)?,
+        Commands::List { trashed, long } => list(*trashed, *long)?,
+        Commands::Exec { name, command } => exec(name, command)?,
+        Commands::Completions { shell } => completions(*shell),
+        Commands::Sweep => sweep()?,
     }
 
     Ok(())
@@ -115,12 +216,309 @@ fn is_valid_entry(entry: &str) -> bool {
     re.is_match(entry)
 }
 
+// Locking
+
+const LOCK_FILE_NAME: &str = "briefcase.lock";
+const LOCK_MAX_ATTEMPTS: u32 = 5;
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+fn get_hostname() -> String {
+    process::Command::new("hostname")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn is_pid_running(pid: u32) -> bool {
+    if pid == 0 {
+        return false;
+    }
+    Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+fn lock_is_stale(payload: &str) -> bool {
+    let Some((host, pid)) = payload.split_once(':') else {
+        return false;
+    };
+    let Ok(pid) = pid.parse::<u32>() else {
+        return false;
+    };
+    host == get_hostname() && !is_pid_running(pid)
+}
+
+/// Acquire an exclusive lock file in `dir`, run `f`, then release the lock.
+///
+/// Acquisition uses `O_EXCL`-style atomic creation (`create_new`). A lock
+/// left by a dead process on this host is removed and creation retried at
+/// once; a lock held by anyone else is retried after `LOCK_RETRY_DELAY`, for
+/// `LOCK_MAX_ATTEMPTS` attempts in all. Returns `BriefcaseError::LockHeld` if
+/// the lock is still held after all attempts.
+fn try_with_lock_no_wait<T>(
+    dir: &Path,
+    lock_name: &str,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let lock_path = dir.join(lock_name);
+    let payload = format!("{}:{}", get_hostname(), process::id());
+
+    let mut acquired = false;
+    for attempt in 0..LOCK_MAX_ATTEMPTS {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock_path)
+        {
+            Ok(mut lock_file) => {
+                lock_file.write_all(payload.as_bytes())?;
+                acquired = true;
+                break;
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                if let Ok(existing) = fs::read_to_string(&lock_path) {
+                    if lock_is_stale(&existing) {
+                        let _ = fs::remove_file(&lock_path);
+                        continue;
+                    }
+                }
+                if attempt + 1 < LOCK_MAX_ATTEMPTS {
+                    thread::sleep(LOCK_RETRY_DELAY);
+                }
+            }
+            Err(e) => return Err(BriefcaseError::Io(e)),
+        }
+    }
+
+    if !acquired {
+        return Err(BriefcaseError::LockHeld(lock_name.to_string()));
+    }
+
+    let result = f();
+    let _ = fs::remove_file(&lock_path);
+    result
+}
+
+// Typed value sidecars
+
+const META_SUFFIX: &str = ".meta";
+const RESERVED_NAMES: [&str; 2] = [LOCK_FILE_NAME, TRASH_DIR_NAME];
+
+fn meta_path(briefcase: &Path, name: &str) -> PathBuf {
+    briefcase.join(format!("{}{}", name, META_SUFFIX))
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// The optional type and expiry recorded alongside an entry. Older sidecars
+/// contain only a bare type label (e.g. `int`); newer ones are `key=value`
+/// lines so both can be stored in the same file.
+#[derive(Default)]
+struct EntryMeta {
+    value_type: Option<ValueType>,
+    expires_at: Option<u64>,
+}
+
+impl EntryMeta {
+    fn is_empty(&self) -> bool {
+        self.value_type.is_none() && self.expires_at.is_none()
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut meta = EntryMeta::default();
+        let mut saw_kv = false;
+        for line in content.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                saw_kv = true;
+                match key {
+                    "type" => meta.value_type = ValueType::parse_label(value),
+                    "expires" => meta.expires_at = value.parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+        if !saw_kv {
+            meta.value_type = ValueType::parse_label(content.trim());
+        }
+        meta
+    }
+}
+
+impl std::fmt::Display for EntryMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(value_type) = self.value_type {
+            writeln!(f, "type={}", value_type.as_str())?;
+        }
+        if let Some(expires_at) = self.expires_at {
+            writeln!(f, "expires={}", expires_at)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_entry_meta(briefcase: &Path, name: &str) -> EntryMeta {
+    fs::read_to_string(meta_path(briefcase, name))
+        .ok()
+        .map(|content| EntryMeta::parse(&content))
+        .unwrap_or_default()
+}
+
+fn is_entry_expired(briefcase: &Path, name: &str) -> bool {
+    read_entry_meta(briefcase, name)
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now_unix())
+}
+
+/// Remove an entry's data file and sidecar without involving the trash;
+/// used for lazily sweeping expired entries.
+fn delete_expired_entry(briefcase: &Path, name: &str) {
+    let _ = fs::remove_file(briefcase.join(name));
+    let _ = fs::remove_file(meta_path(briefcase, name));
+}
+
+/// Check whether an entry is expired and, if so, delete it under the lock.
+/// Locking keeps this from racing a concurrent `set` that just wrote a
+/// fresh value/sidecar for the same name. Returns whether it was expired.
+fn expire_if_needed(briefcase: &Path, name: &str) -> Result<bool> {
+    try_with_lock_no_wait(briefcase, LOCK_FILE_NAME, || {
+        if is_entry_expired(briefcase, name) {
+            delete_expired_entry(briefcase, name);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    })
+}
+
+/// True for bookkeeping files (sidecars, the lock file, the trash dir) that
+/// should never show up as an entry in `list`.
+fn is_internal_name(name: &str) -> bool {
+    name.ends_with(META_SUFFIX) || RESERVED_NAMES.contains(&name)
+}
+
+/// Parse human-friendly durations like `30m`, `2h`, or `7d` for `--ttl`.
+fn parse_ttl(input: &str) -> std::result::Result<Duration, String> {
+    let input = input.trim();
+    let split_at = input
+        .len()
+        .checked_sub(1)
+        .ok_or_else(|| format!("invalid ttl: {}", input))?;
+    let (amount, unit) = input.split_at(split_at);
+
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        _ => {
+            return Err(format!(
+                "invalid ttl unit in {} (expected s, m, h, or d)",
+                input
+            ))
+        }
+    };
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid ttl: {}", input))?;
+
+    let seconds = amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("ttl too large: {}", input))?;
+    Ok(Duration::from_secs(seconds))
+}
+
+// Trash
+
+const TRASH_DIR_NAME: &str = ".trash";
+const TRASH_FILES_DIR_NAME: &str = "files";
+const TRASH_INFO_DIR_NAME: &str = "info";
+
+fn trash_files_dir(briefcase: &Path) -> PathBuf {
+    briefcase.join(TRASH_DIR_NAME).join(TRASH_FILES_DIR_NAME)
+}
+
+fn trash_info_dir(briefcase: &Path) -> PathBuf {
+    briefcase.join(TRASH_DIR_NAME).join(TRASH_INFO_DIR_NAME)
+}
+
+fn trash_info_path(briefcase: &Path, name: &str) -> PathBuf {
+    trash_info_dir(briefcase).join(format!("{}.trashinfo", name))
+}
+
+/// Move an entry into the trash, recording its original name and deletion
+/// time in a companion `.trashinfo` file, following the Freedesktop trash
+/// spec layout (`files/` + `info/`).
+fn trash_entry(briefcase: &Path, name: &str) -> Result<()> {
+    let files_dir = trash_files_dir(briefcase);
+    let info_dir = trash_info_dir(briefcase);
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    let dest = files_dir.join(name);
+    if dest.exists() {
+        return Err(BriefcaseError::EntryExists(format!(
+            "{} is already in the trash; restore or purge it first",
+            name
+        )));
+    }
+    fs::rename(briefcase.join(name), dest)?;
+
+    let meta_src = meta_path(briefcase, name);
+    if meta_src.exists() {
+        fs::rename(meta_src, meta_path(&files_dir, name))?;
+    }
+
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        name,
+        Utc::now().to_rfc3339()
+    );
+    fs::write(trash_info_path(briefcase, name), info)?;
+    Ok(())
+}
+
+/// Move an entry out of the trash and remove its `.trashinfo` metadata.
+fn untrash_entry(briefcase: &Path, name: &str) -> Result<()> {
+    let info_path = trash_info_path(briefcase, name);
+    if !info_path.exists() {
+        return Err(BriefcaseError::EntryNotFound(name.to_string()));
+    }
+
+    let files_dir = trash_files_dir(briefcase);
+    let dest = briefcase.join(name);
+    if dest.exists() {
+        return Err(BriefcaseError::EntryExists(format!(
+            "{} already exists; remove or rename it before restoring",
+            name
+        )));
+    }
+    fs::rename(files_dir.join(name), dest)?;
+
+    let trashed_meta = meta_path(&files_dir, name);
+    if trashed_meta.exists() {
+        fs::rename(trashed_meta, meta_path(briefcase, name))?;
+    }
+
+    fs::remove_file(info_path)?;
+    Ok(())
+}
+
 // Command functions
 
 fn version() {
     println!("Briefcase {}", VERSION);
 }
 
+fn completions(shell: Shell) {
+    clap_complete::generate(shell, &mut Cli::command(), "briefcase", &mut io::stdout());
+}
+
 fn info() -> Result<()> {
     let temp_info = get_temp_dir();
     let dir_name = get_briefcase_dir_name();
@@ -130,24 +528,47 @@ fn info() -> Result<()> {
     Ok(())
 }
 
-fn set(name: &str, value: &str) -> Result<()> {
+fn set(
+    name: &str,
+    value: &str,
+    value_type: Option<ValueType>,
+    ttl: Option<Duration>,
+) -> Result<()> {
     if !is_valid_entry(name) {
         return Err(BriefcaseError::InvalidEntry(name.to_string()));
     }
+    if let Some(value_type) = value_type {
+        value_type.validate(value)?;
+    }
+    let expires_at = ttl
+        .map(|ttl| {
+            now_unix()
+                .checked_add(ttl.as_secs())
+                .ok_or_else(|| BriefcaseError::InvalidValue("ttl too large".to_string()))
+        })
+        .transpose()?;
 
     let briefcase = get_briefcase_dir();
     fs::create_dir_all(&briefcase)?;
 
-    let file_path = briefcase.join(name);
-    fs::write(file_path, value)?;
-    Ok(())
-}
+    try_with_lock_no_wait(&briefcase, LOCK_FILE_NAME, || {
+        fs::write(briefcase.join(name), value)?;
 
-fn get(name: &str) -> Result<()> {
-    if !is_valid_entry(name) {
-        return Err(BriefcaseError::InvalidEntry(name.to_string()));
-    }
+        let sidecar = meta_path(&briefcase, name);
+        let meta = EntryMeta {
+            value_type,
+            expires_at,
+        };
+        if meta.is_empty() {
+            let _ = fs::remove_file(sidecar);
+        } else {
+            fs::write(sidecar, meta.to_string())?;
+        }
+        Ok(())
+    })
+}
 
+fn read_entry(name: &str) -> Result<String> {
     let file_path = get_briefcase_dir().join(name);
     let mut file = fs::File::open(&file_path).map_err(|e| {
         if e.kind() == io::ErrorKind::NotFound {
@@ -159,7 +580,33 @@ fn get(name: &str) -> Result<()> {
 
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
-    print!("{}", contents);
+    Ok(contents)
+}
+
+fn get(name: &str, json: bool) -> Result<()> {
+    if !is_valid_entry(name) {
+        return Err(BriefcaseError::InvalidEntry(name.to_string()));
+    }
+
+    let briefcase = get_briefcase_dir();
+    if expire_if_needed(&briefcase, name)? {
+        return Err(BriefcaseError::EntryNotFound(name.to_string()));
+    }
+
+    let value = read_entry(name)?;
+    if json {
+        let value_type = read_entry_meta(&briefcase, name)
+            .value_type
+            .unwrap_or(ValueType::String);
+        let payload = serde_json::json!({
+            "name": name,
+            "type": value_type.as_str(),
+            "value": value,
+        });
+        println!("{}", payload);
+    } else {
+        print!("{}", value);
+    }
     Ok(())
 }
 
@@ -176,33 +623,154 @@ fn purge(force: bool) -> Result<()> {
     }
 
     let briefcase = get_briefcase_dir();
-    fs::remove_dir_all(briefcase)?;
-    println!("Briefcase data purged successfully");
-    Ok(())
+    fs::create_dir_all(&briefcase)?;
+    try_with_lock_no_wait(&briefcase, LOCK_FILE_NAME, || {
+        fs::remove_dir_all(&briefcase)?;
+        println!("Briefcase data purged successfully");
+        Ok(())
+    })
 }
 
-fn remove(name: &str) -> Result<()> {
+fn remove(name: &str, permanent: bool) -> Result<()> {
     if !is_valid_entry(name) {
         return Err(BriefcaseError::InvalidEntry(name.to_string()));
     }
 
-    let file_path = get_briefcase_dir().join(name);
-    fs::remove_file(file_path)?;
-    println!("Removed {}", name);
-    Ok(())
+    let briefcase = get_briefcase_dir();
+    try_with_lock_no_wait(&briefcase, LOCK_FILE_NAME, || {
+        let file_path = briefcase.join(name);
+        if !file_path.exists() {
+            return Err(BriefcaseError::EntryNotFound(name.to_string()));
+        }
+
+        if permanent {
+            fs::remove_file(file_path)?;
+        } else {
+            trash_entry(&briefcase, name)?;
+        }
+        println!("Removed {}", name);
+        Ok(())
+    })
 }
 
-fn list() -> Result<()> {
+fn restore(name: &str) -> Result<()> {
+    if !is_valid_entry(name) {
+        return Err(BriefcaseError::InvalidEntry(name.to_string()));
+    }
+
     let briefcase = get_briefcase_dir();
-    let entries = fs::read_dir(briefcase)?;
+    try_with_lock_no_wait(&briefcase, LOCK_FILE_NAME, || {
+        untrash_entry(&briefcase, name)?;
+        println!("Restored {}", name);
+        Ok(())
+    })
+}
+
+fn list(trashed: bool, long: bool) -> Result<()> {
+    let dir = if trashed {
+        trash_files_dir(&get_briefcase_dir())
+    } else {
+        get_briefcase_dir()
+    };
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
 
     for entry in entries {
         let entry = entry?;
-        println!("{}", entry.file_name().to_string_lossy());
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_internal_name(&name) {
+            continue;
+        }
+        if !trashed && expire_if_needed(&dir, &name)? {
+            continue;
+        }
+
+        if long {
+            let size = entry.metadata()?.len();
+            let value_type = read_entry_meta(&dir, &name)
+                .value_type
+                .unwrap_or(ValueType::String);
+            println!("{}\t{}\t{} bytes", name, value_type.as_str(), size);
+        } else {
+            println!("{}", name);
+        }
     }
     Ok(())
 }
 
+fn sweep() -> Result<()> {
+    let briefcase = get_briefcase_dir();
+    try_with_lock_no_wait(&briefcase, LOCK_FILE_NAME, || {
+        let entries = match fs::read_dir(&briefcase) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut swept = 0;
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_internal_name(&name) {
+                continue;
+            }
+            if is_entry_expired(&briefcase, &name) {
+                delete_expired_entry(&briefcase, &name);
+                swept += 1;
+            }
+        }
+        println!(
+            "Swept {} expired entr{}",
+            swept,
+            if swept == 1 { "y" } else { "ies" }
+        );
+        Ok(())
+    })
+}
+
+const EXEC_PLACEHOLDER: &str = "{}";
+
+fn exec(name: &str, command: &[String]) -> Result<()> {
+    if !is_valid_entry(name) {
+        return Err(BriefcaseError::InvalidEntry(name.to_string()));
+    }
+    if command.is_empty() {
+        return Err(BriefcaseError::InvalidCommand(
+            "no command provided".to_string(),
+        ));
+    }
+
+    let briefcase = get_briefcase_dir();
+    if expire_if_needed(&briefcase, name)? {
+        return Err(BriefcaseError::EntryNotFound(name.to_string()));
+    }
+
+    let value = read_entry(name)?;
+    let has_placeholder = command.iter().any(|arg| arg.contains(EXEC_PLACEHOLDER));
+
+    let mut parts: Vec<String> = command
+        .iter()
+        .map(|arg| {
+            if has_placeholder {
+                arg.replace(EXEC_PLACEHOLDER, &value)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect();
+    if !has_placeholder {
+        parts.push(value);
+    }
+
+    let (program, args) = parts.split_first().expect("checked non-empty above");
+    let status = process::Command::new(program).args(args).status()?;
+    process::exit(status.code().unwrap_or(1));
+}
+
 // Error handling
 
 impl std::fmt::Display for BriefcaseError {
@@ -211,8 +779,117 @@ impl std::fmt::Display for BriefcaseError {
             BriefcaseError::Io(err) => write!(f, "IO error: {}", err),
             BriefcaseError::InvalidEntry(entry) => write!(f, "Invalid entry name: {}", entry),
             BriefcaseError::EntryNotFound(entry) => write!(f, "Entry not found: {}", entry),
+            BriefcaseError::LockHeld(name) => {
+                write!(f, "Another process holds the lock: {}", name)
+            }
+            BriefcaseError::InvalidCommand(msg) => write!(f, "Invalid command: {}", msg),
+            BriefcaseError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+            BriefcaseError::EntryExists(msg) => write!(f, "Entry already exists: {}", msg),
         }
     }
 }
 
 impl std::error::Error for BriefcaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_briefcase_dir(label: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let dir = env::temp_dir().join(format!(
+            "briefcase-test-{}-{}-{}",
+            label,
+            process::id(),
+            unique
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn lock_is_stale_detects_dead_local_pid() {
+        // A pid this high is vanishingly unlikely to be running.
+        let payload = format!("{}:{}", get_hostname(), 999_999);
+        assert!(lock_is_stale(&payload));
+    }
+
+    #[test]
+    fn lock_is_stale_keeps_live_local_pid() {
+        let payload = format!("{}:{}", get_hostname(), process::id());
+        assert!(!lock_is_stale(&payload));
+    }
+
+    #[test]
+    fn lock_is_stale_ignores_other_hosts() {
+        let payload = format!("{}:{}", "some-other-host", 999_999);
+        assert!(!lock_is_stale(&payload));
+    }
+
+    #[test]
+    fn lock_is_stale_ignores_malformed_payload() {
+        assert!(!lock_is_stale("not-a-valid-payload"));
+    }
+
+    #[test]
+    fn parse_ttl_accepts_known_units() {
+        assert_eq!(parse_ttl("10s").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_ttl("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_ttl("2h").unwrap(), Duration::from_secs(2 * 60 * 60));
+        assert_eq!(parse_ttl("7d").unwrap(), Duration::from_secs(7 * 24 * 60 * 60));
+    }
+
+    #[test]
+    fn parse_ttl_rejects_unknown_unit() {
+        assert!(parse_ttl("10x").is_err());
+    }
+
+    #[test]
+    fn parse_ttl_rejects_overflowing_amount() {
+        assert!(parse_ttl(&format!("{}d", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn trash_entry_refuses_to_clobber_existing_trashed_entry() {
+        let briefcase = temp_briefcase_dir("trash-clobber");
+
+        fs::write(briefcase.join("foo"), "first").unwrap();
+        trash_entry(&briefcase, "foo").unwrap();
+        assert_eq!(
+            fs::read_to_string(trash_files_dir(&briefcase).join("foo")).unwrap(),
+            "first"
+        );
+
+        fs::write(briefcase.join("foo"), "second").unwrap();
+        let result = trash_entry(&briefcase, "foo");
+        assert!(matches!(result, Err(BriefcaseError::EntryExists(_))));
+        assert_eq!(
+            fs::read_to_string(trash_files_dir(&briefcase).join("foo")).unwrap(),
+            "first"
+        );
+
+        fs::remove_dir_all(&briefcase).unwrap();
+    }
+
+    #[test]
+    fn untrash_entry_refuses_to_clobber_existing_live_entry() {
+        let briefcase = temp_briefcase_dir("untrash-clobber");
+
+        fs::write(briefcase.join("foo"), "trashed").unwrap();
+        trash_entry(&briefcase, "foo").unwrap();
+
+        fs::write(briefcase.join("foo"), "live").unwrap();
+        let result = untrash_entry(&briefcase, "foo");
+        assert!(matches!(result, Err(BriefcaseError::EntryExists(_))));
+        assert_eq!(fs::read_to_string(briefcase.join("foo")).unwrap(), "live");
+        assert_eq!(
+            fs::read_to_string(trash_files_dir(&briefcase).join("foo")).unwrap(),
+            "trashed"
+        );
+
+        fs::remove_dir_all(&briefcase).unwrap();
+    }
+}